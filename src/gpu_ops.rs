@@ -3,11 +3,169 @@ use std::rc::Rc;
 
 use deno_core::op2;
 use deno_core::OpState;
+use wgpu::util::DeviceExt;
 
-use crate::gpu_state::{GpuState, WindowConfig};
+use crate::gpu_state::{GpuState, GuiInteraction, GuiWidgetDesc, WindowConfig};
 
 type SharedGpuState = Rc<RefCell<Option<GpuState>>>;
 
+/// Description of a single vertex attribute within a `VertexBufferLayoutDesc`,
+/// mirroring `wgpu::VertexAttribute` for JSON transport from JS.
+#[derive(serde::Deserialize)]
+struct VertexAttributeDesc {
+    format: String,
+    offset: u64,
+    shader_location: u32,
+}
+
+/// Description of a vertex buffer's layout, mirroring `wgpu::VertexBufferLayout`
+/// for JSON transport from JS.
+#[derive(serde::Deserialize)]
+struct VertexBufferLayoutDesc {
+    array_stride: u64,
+    step_mode: String,
+    attributes: Vec<VertexAttributeDesc>,
+}
+
+/// Map a buffer usage string from JS ("vertex" / "index" / "uniform" /
+/// "storage") to the matching `wgpu::BufferUsages`, always including
+/// `COPY_DST` so the buffer can later be updated via `op_gpu_write_buffer`.
+/// Storage buffers also get `COPY_SRC` so `op_gpu_read_buffer` can copy them.
+fn buffer_usage_from_str(usage: &str) -> wgpu::BufferUsages {
+    match usage {
+        "vertex" => wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        "index" => wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        "uniform" => wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        "storage" => {
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC
+        }
+        other => panic!("Unknown buffer usage: {other}"),
+    }
+}
+
+/// Map a vertex format string (using wgpu's own naming, e.g. "float32x3")
+/// to a `wgpu::VertexFormat`.
+fn vertex_format_from_str(format: &str) -> wgpu::VertexFormat {
+    match format {
+        "uint32" => wgpu::VertexFormat::Uint32,
+        "sint32" => wgpu::VertexFormat::Sint32,
+        "float32" => wgpu::VertexFormat::Float32,
+        "float32x2" => wgpu::VertexFormat::Float32x2,
+        "float32x3" => wgpu::VertexFormat::Float32x3,
+        "float32x4" => wgpu::VertexFormat::Float32x4,
+        other => panic!("Unknown vertex format: {other}"),
+    }
+}
+
+/// Map shader stage names ("vertex" / "fragment" / "compute") to the
+/// matching `wgpu::ShaderStages`, OR-ed together.
+fn shader_stages_from_strs(stages: &[String]) -> wgpu::ShaderStages {
+    stages.iter().fold(wgpu::ShaderStages::NONE, |acc, stage| {
+        acc | match stage.as_str() {
+            "vertex" => wgpu::ShaderStages::VERTEX,
+            "fragment" => wgpu::ShaderStages::FRAGMENT,
+            "compute" => wgpu::ShaderStages::COMPUTE,
+            other => panic!("Unknown shader stage: {other}"),
+        }
+    })
+}
+
+/// Description of a bind group layout entry. `resource_type` is `"uniform"`
+/// for a uniform buffer, `"storage"` for a storage buffer (read-write unless
+/// `read_only` is set), or `"sampler"`/`"texture"`.
+#[derive(serde::Deserialize)]
+struct BindGroupLayoutEntryDesc {
+    binding: u32,
+    visibility: Vec<String>,
+    resource_type: String,
+    #[serde(default)]
+    read_only: bool,
+}
+
+/// The concrete resource a bind group entry binds, tagged by `type` on the JS side.
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum BindGroupResourceDesc {
+    Buffer { id: u32 },
+    Texture { id: u32 },
+    Sampler { id: u32 },
+}
+
+/// Description of a concrete bind group entry binding a buffer, texture, or
+/// sampler handle to a binding index.
+#[derive(serde::Deserialize)]
+struct BindGroupEntryDesc {
+    binding: u32,
+    resource: BindGroupResourceDesc,
+}
+
+/// Depth/stencil options for a render pipeline. Defaults to `Depth32Float` /
+/// `LessEqual` when a field is omitted, matching the learn-wgpu depth tutorial.
+#[derive(serde::Deserialize)]
+struct DepthStencilDesc {
+    depth_format: Option<String>,
+    depth_compare: Option<String>,
+}
+
+/// Primitive assembly options for a render pipeline. Defaults to
+/// `TriangleList` with no culling when a field is omitted.
+#[derive(serde::Deserialize)]
+struct PrimitiveStateDesc {
+    topology: Option<String>,
+    cull_mode: Option<String>,
+}
+
+/// Map a primitive topology string (WebGPU naming, e.g. "triangle-strip")
+/// to a `wgpu::PrimitiveTopology`.
+fn primitive_topology_from_str(topology: &str) -> wgpu::PrimitiveTopology {
+    match topology {
+        "point-list" => wgpu::PrimitiveTopology::PointList,
+        "line-list" => wgpu::PrimitiveTopology::LineList,
+        "line-strip" => wgpu::PrimitiveTopology::LineStrip,
+        "triangle-list" => wgpu::PrimitiveTopology::TriangleList,
+        "triangle-strip" => wgpu::PrimitiveTopology::TriangleStrip,
+        other => panic!("Unknown primitive topology: {other}"),
+    }
+}
+
+/// Map a cull mode string ("none" / "front" / "back") to the matching
+/// `wgpu::Face`, or `None` for "none" (the standard WebGPU default of no
+/// culling).
+fn cull_mode_from_str(cull_mode: &str) -> Option<wgpu::Face> {
+    match cull_mode {
+        "none" => None,
+        "front" => Some(wgpu::Face::Front),
+        "back" => Some(wgpu::Face::Back),
+        other => panic!("Unknown cull mode: {other}"),
+    }
+}
+
+/// Map a depth format string to a `wgpu::TextureFormat`.
+fn depth_format_from_str(format: &str) -> wgpu::TextureFormat {
+    match format {
+        "depth32float" => wgpu::TextureFormat::Depth32Float,
+        "depth24plus" => wgpu::TextureFormat::Depth24Plus,
+        "depth24plus-stencil8" => wgpu::TextureFormat::Depth24PlusStencil8,
+        other => panic!("Unknown depth format: {other}"),
+    }
+}
+
+/// Map a depth compare function string (WebGPU naming, e.g. "less-equal")
+/// to a `wgpu::CompareFunction`.
+fn compare_function_from_str(compare: &str) -> wgpu::CompareFunction {
+    match compare {
+        "never" => wgpu::CompareFunction::Never,
+        "less" => wgpu::CompareFunction::Less,
+        "equal" => wgpu::CompareFunction::Equal,
+        "less-equal" => wgpu::CompareFunction::LessEqual,
+        "greater" => wgpu::CompareFunction::Greater,
+        "not-equal" => wgpu::CompareFunction::NotEqual,
+        "greater-equal" => wgpu::CompareFunction::GreaterEqual,
+        "always" => wgpu::CompareFunction::Always,
+        other => panic!("Unknown depth compare function: {other}"),
+    }
+}
+
 /// Store window configuration. Called from JS before the event loop starts.
 #[op2(fast)]
 pub fn op_gpu_create_window(
@@ -45,7 +203,159 @@ pub fn op_gpu_create_shader_module(state: &mut OpState, #[string] code: String)
     id
 }
 
-/// Create a render pipeline using a shader module handle. Returns a pipeline handle ID.
+/// Upload a byte payload to the GPU as a vertex, index, uniform, or storage
+/// buffer (`usage` is one of `"vertex"`, `"index"`, `"uniform"`, `"storage"`).
+/// Returns a handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_buffer(
+    state: &mut OpState,
+    #[buffer] data: &[u8],
+    #[string] usage: String,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createBuffer called before setup");
+
+    let buffer = gpu
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("User Buffer"),
+            contents: data,
+            usage: buffer_usage_from_str(&usage),
+        });
+
+    let id = gpu.buffers.len() as u32;
+    gpu.buffers.push(buffer);
+    id
+}
+
+/// Create a bind group layout from a description of its entries (binding
+/// index, visibility stage(s), and resource type). Returns a handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_bind_group_layout(
+    state: &mut OpState,
+    #[serde] entries: Vec<BindGroupLayoutEntryDesc>,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createBindGroupLayout called before setup");
+
+    let layout_entries: Vec<wgpu::BindGroupLayoutEntry> = entries
+        .iter()
+        .map(|entry| wgpu::BindGroupLayoutEntry {
+            binding: entry.binding,
+            visibility: shader_stages_from_strs(&entry.visibility),
+            ty: match entry.resource_type.as_str() {
+                "uniform" => wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                "storage" => wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage {
+                        read_only: entry.read_only,
+                    },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                "sampler" => wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                "texture" => wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                other => panic!("Unknown bind group layout resource type: {other}"),
+            },
+            count: None,
+        })
+        .collect();
+
+    let layout = gpu
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("User Bind Group Layout"),
+            entries: &layout_entries,
+        });
+
+    let id = gpu.bind_group_layouts.len() as u32;
+    gpu.bind_group_layouts.push(layout);
+    id
+}
+
+/// Create a bind group binding concrete buffer, texture, or sampler handles
+/// to a bind group layout. Returns a handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_bind_group(
+    state: &mut OpState,
+    #[smi] layout_id: u32,
+    #[serde] entries: Vec<BindGroupEntryDesc>,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createBindGroup called before setup");
+
+    let layout = &gpu.bind_group_layouts[layout_id as usize];
+    let bind_group_entries: Vec<wgpu::BindGroupEntry> = entries
+        .iter()
+        .map(|entry| wgpu::BindGroupEntry {
+            binding: entry.binding,
+            resource: match &entry.resource {
+                BindGroupResourceDesc::Buffer { id } => {
+                    gpu.buffers[*id as usize].as_entire_binding()
+                }
+                BindGroupResourceDesc::Texture { id } => {
+                    wgpu::BindingResource::TextureView(&gpu.texture_views[*id as usize])
+                }
+                BindGroupResourceDesc::Sampler { id } => {
+                    wgpu::BindingResource::Sampler(&gpu.samplers[*id as usize])
+                }
+            },
+        })
+        .collect();
+
+    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("User Bind Group"),
+        layout,
+        entries: &bind_group_entries,
+    });
+
+    let id = gpu.bind_groups.len() as u32;
+    gpu.bind_groups.push(bind_group);
+    id
+}
+
+/// Copy a byte payload into an existing buffer at the given byte offset via
+/// `queue.write_buffer`. Used to update uniforms (camera matrices, time,
+/// resolution, ...) every frame.
+#[op2(fast)]
+pub fn op_gpu_write_buffer(
+    state: &mut OpState,
+    #[smi] buffer_id: u32,
+    #[number] offset: u64,
+    #[buffer] data: &[u8],
+) {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - writeBuffer called before setup");
+
+    gpu.queue
+        .write_buffer(&gpu.buffers[buffer_id as usize], offset, data);
+}
+
+/// Create a render pipeline using a shader module handle, a description of
+/// the vertex buffer layouts it expects, and the bind group layouts its
+/// shaders read uniforms/textures through. Returns a pipeline handle ID.
 #[op2(fast)]
 #[smi]
 pub fn op_gpu_create_render_pipeline(
@@ -53,6 +363,10 @@ pub fn op_gpu_create_render_pipeline(
     #[smi] shader_module_id: u32,
     #[string] vertex_entry: String,
     #[string] fragment_entry: String,
+    #[serde] vertex_buffers: Vec<VertexBufferLayoutDesc>,
+    #[serde] bind_group_layout_ids: Vec<u32>,
+    #[serde] depth_stencil: Option<DepthStencilDesc>,
+    #[serde] primitive: Option<PrimitiveStateDesc>,
 ) -> u32 {
     let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
     let mut gpu_state_opt = gpu_state_rc.borrow_mut();
@@ -63,11 +377,83 @@ pub fn op_gpu_create_render_pipeline(
     let shader = &gpu.shader_modules[shader_module_id as usize];
     let surface_format = gpu.config.format;
 
+    let vertex_attributes: Vec<Vec<wgpu::VertexAttribute>> = vertex_buffers
+        .iter()
+        .map(|layout| {
+            layout
+                .attributes
+                .iter()
+                .map(|attr| wgpu::VertexAttribute {
+                    format: vertex_format_from_str(&attr.format),
+                    offset: attr.offset,
+                    shader_location: attr.shader_location,
+                })
+                .collect()
+        })
+        .collect();
+
+    let vertex_buffer_layouts: Vec<wgpu::VertexBufferLayout> = vertex_buffers
+        .iter()
+        .zip(vertex_attributes.iter())
+        .map(|(layout, attributes)| wgpu::VertexBufferLayout {
+            array_stride: layout.array_stride,
+            step_mode: match layout.step_mode.as_str() {
+                "instance" => wgpu::VertexStepMode::Instance,
+                _ => wgpu::VertexStepMode::Vertex,
+            },
+            attributes,
+        })
+        .collect();
+
+    let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = bind_group_layout_ids
+        .iter()
+        .map(|id| &gpu.bind_group_layouts[*id as usize])
+        .collect();
+
+    let has_depth = depth_stencil.is_some();
+    let depth_stencil = depth_stencil.map(|desc| {
+        let format = desc
+            .depth_format
+            .as_deref()
+            .map(depth_format_from_str)
+            .unwrap_or(wgpu::TextureFormat::Depth32Float);
+        let depth_compare = desc
+            .depth_compare
+            .as_deref()
+            .map(compare_function_from_str)
+            .unwrap_or(wgpu::CompareFunction::LessEqual);
+
+        gpu.ensure_depth_texture(format);
+
+        wgpu::DepthStencilState {
+            format,
+            depth_write_enabled: true,
+            depth_compare,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }
+    });
+
+    let primitive_state = primitive
+        .map(|desc| wgpu::PrimitiveState {
+            topology: desc
+                .topology
+                .as_deref()
+                .map(primitive_topology_from_str)
+                .unwrap_or(wgpu::PrimitiveTopology::TriangleList),
+            cull_mode: desc.cull_mode.as_deref().and_then(cull_mode_from_str),
+            ..Default::default()
+        })
+        .unwrap_or(wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        });
+
     let pipeline_layout = gpu
         .device
         .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Pipeline Layout"),
-            bind_group_layouts: &[],
+            bind_group_layouts: &bind_group_layouts,
             ..Default::default()
         });
 
@@ -79,7 +465,7 @@ pub fn op_gpu_create_render_pipeline(
             vertex: wgpu::VertexState {
                 module: shader,
                 entry_point: Some(&vertex_entry),
-                buffers: &[],
+                buffers: &vertex_buffer_layouts,
                 compilation_options: Default::default(),
             },
             fragment: Some(wgpu::FragmentState {
@@ -92,11 +478,8 @@ pub fn op_gpu_create_render_pipeline(
                 })],
                 compilation_options: Default::default(),
             }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
+            primitive: primitive_state,
+            depth_stencil,
             multisample: wgpu::MultisampleState::default(),
             multiview_mask: None,
             cache: None,
@@ -104,6 +487,7 @@ pub fn op_gpu_create_render_pipeline(
 
     let id = gpu.render_pipelines.len() as u32;
     gpu.render_pipelines.push(pipeline);
+    gpu.render_pipeline_has_depth.push(has_depth);
     id
 }
 
@@ -113,6 +497,8 @@ pub fn op_gpu_create_render_pipeline(
 pub fn op_gpu_draw_frame(
     state: &mut OpState,
     #[smi] pipeline_id: u32,
+    #[serde] vertex_buffer_ids: Vec<u32>,
+    #[serde] bind_group_ids: Vec<u32>,
     r: f64,
     g: f64,
     b: f64,
@@ -150,6 +536,23 @@ pub fn op_gpu_draw_frame(
             label: Some("Frame Encoder"),
         });
 
+    let pipeline_has_depth = gpu
+        .render_pipeline_has_depth
+        .get(pipeline_id as usize)
+        .copied()
+        .unwrap_or(false);
+    let depth_stencil_attachment = pipeline_has_depth
+        .then(|| gpu.depth_view.as_ref())
+        .flatten()
+        .map(|view| wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        });
+
     {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Render Pass"),
@@ -167,7 +570,7 @@ pub fn op_gpu_draw_frame(
                     store: wgpu::StoreOp::Store,
                 },
             })],
-            depth_stencil_attachment: None,
+            depth_stencil_attachment,
             timestamp_writes: None,
             occlusion_query_set: None,
             multiview_mask: None,
@@ -175,9 +578,536 @@ pub fn op_gpu_draw_frame(
 
         let pipeline = &gpu.render_pipelines[pipeline_id as usize];
         render_pass.set_pipeline(pipeline);
+        for (index, bind_group_id) in bind_group_ids.iter().enumerate() {
+            render_pass.set_bind_group(
+                index as u32,
+                &gpu.bind_groups[*bind_group_id as usize],
+                &[],
+            );
+        }
+        for (slot, buffer_id) in vertex_buffer_ids.iter().enumerate() {
+            let buffer = &gpu.buffers[*buffer_id as usize];
+            render_pass.set_vertex_buffer(slot as u32, buffer.slice(..));
+        }
         render_pass.draw(0..vertex_count, 0..instance_count);
     }
 
+    render_egui_overlay(gpu, &mut encoder, &view);
+
+    gpu.queue.submit(std::iter::once(encoder.finish()));
+    surface_texture.present();
+}
+
+/// If a JS `gui()` callback produced a pending egui frame this frame, upload
+/// its textures and tessellated shapes and render them into `view` with a
+/// `Load` op, in the same encoder as the user's draw call.
+fn render_egui_overlay(
+    gpu: &mut GpuState,
+    encoder: &mut wgpu::CommandEncoder,
+    view: &wgpu::TextureView,
+) {
+    let Some((paint_jobs, textures_delta, pixels_per_point)) = gpu.pending_egui_output.take()
+    else {
+        return;
+    };
+
+    for (id, image_delta) in &textures_delta.set {
+        gpu.egui_renderer
+            .update_texture(&gpu.device, &gpu.queue, *id, image_delta);
+    }
+
+    let screen_descriptor = egui_wgpu::ScreenDescriptor {
+        size_in_pixels: [gpu.config.width, gpu.config.height],
+        pixels_per_point,
+    };
+
+    gpu.egui_renderer.update_buffers(
+        &gpu.device,
+        &gpu.queue,
+        encoder,
+        &paint_jobs,
+        &screen_descriptor,
+    );
+
+    {
+        let mut egui_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("egui Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        gpu.egui_renderer
+            .render(&mut egui_pass, &paint_jobs, &screen_descriptor);
+    }
+
+    for id in &textures_delta.free {
+        gpu.egui_renderer.free_texture(id);
+    }
+}
+
+/// Add a static text label to the current egui debug frame.
+#[op2(fast)]
+pub fn op_gui_label(state: &mut OpState, #[string] text: String) {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - guiLabel called before setup");
+
+    gpu.egui_widgets.push(GuiWidgetDesc::Label(text));
+}
+
+/// Add a slider to the current egui debug frame. `value` is the caller's
+/// current value; the return is that value updated by the *previous*
+/// frame's drag, so feeding the result back in each frame round-trips state.
+#[op2(fast)]
+pub fn op_gui_slider(
+    state: &mut OpState,
+    #[string] label: String,
+    value: f64,
+    min: f64,
+    max: f64,
+) -> f64 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - guiSlider called before setup");
+
+    let resolved = match gpu.egui_interactions.get(&label) {
+        Some(GuiInteraction::Slider(v)) => *v,
+        _ => value,
+    };
+    gpu.egui_widgets.push(GuiWidgetDesc::Slider {
+        label,
+        value: resolved,
+        min,
+        max,
+    });
+    resolved
+}
+
+/// Add a button to the current egui debug frame. Returns whether it was
+/// clicked during the *previous* frame's render.
+#[op2(fast)]
+pub fn op_gui_button(state: &mut OpState, #[string] label: String) -> bool {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - guiButton called before setup");
+
+    let clicked = matches!(
+        gpu.egui_interactions.get(&label),
+        Some(GuiInteraction::Button(true))
+    );
+    gpu.egui_widgets.push(GuiWidgetDesc::Button { label });
+    clicked
+}
+
+/// Execute a render frame using indexed drawing: bind the given vertex
+/// buffers and an index buffer, then `draw_indexed`. The index buffer is
+/// interpreted as tightly packed `u32` indices.
+#[op2(fast)]
+pub fn op_gpu_draw_indexed(
+    state: &mut OpState,
+    #[smi] pipeline_id: u32,
+    #[serde] vertex_buffer_ids: Vec<u32>,
+    #[serde] bind_group_ids: Vec<u32>,
+    #[smi] index_buffer_id: u32,
+    r: f64,
+    g: f64,
+    b: f64,
+    a: f64,
+    #[smi] index_count: u32,
+    #[smi] instance_count: u32,
+) {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - drawIndexed called before setup");
+
+    let surface_texture = match gpu.surface.get_current_texture() {
+        Ok(tex) => tex,
+        Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+            gpu.surface.configure(&gpu.device, &gpu.config);
+            gpu.surface
+                .get_current_texture()
+                .expect("Failed to acquire surface texture after reconfigure")
+        }
+        Err(e) => {
+            eprintln!("Surface error: {:?}", e);
+            return;
+        }
+    };
+
+    let view = surface_texture
+        .texture
+        .create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut encoder = gpu
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Indexed Frame Encoder"),
+        });
+
+    let pipeline_has_depth = gpu
+        .render_pipeline_has_depth
+        .get(pipeline_id as usize)
+        .copied()
+        .unwrap_or(false);
+    let depth_stencil_attachment = pipeline_has_depth
+        .then(|| gpu.depth_view.as_ref())
+        .flatten()
+        .map(|view| wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        });
+
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Indexed Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color { r, g, b, a }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+
+        let pipeline = &gpu.render_pipelines[pipeline_id as usize];
+        render_pass.set_pipeline(pipeline);
+        for (index, bind_group_id) in bind_group_ids.iter().enumerate() {
+            render_pass.set_bind_group(
+                index as u32,
+                &gpu.bind_groups[*bind_group_id as usize],
+                &[],
+            );
+        }
+        for (slot, buffer_id) in vertex_buffer_ids.iter().enumerate() {
+            let buffer = &gpu.buffers[*buffer_id as usize];
+            render_pass.set_vertex_buffer(slot as u32, buffer.slice(..));
+        }
+        let index_buffer = &gpu.buffers[index_buffer_id as usize];
+        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..index_count, 0, 0..instance_count);
+    }
+
+    render_egui_overlay(gpu, &mut encoder, &view);
+
     gpu.queue.submit(std::iter::once(encoder.finish()));
     surface_texture.present();
 }
+
+/// Create a texture from raw RGBA bytes, or from an encoded PNG/JPEG payload
+/// decoded via the `image` crate (`encoding` is `"raw"`, `"png"`, or `"jpeg"`;
+/// `width`/`height` are only used for `"raw"` payloads). Uploads the pixels
+/// and creates a default `TextureView`. Returns the texture's handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_texture(
+    state: &mut OpState,
+    #[buffer] data: &[u8],
+    #[smi] width: u32,
+    #[smi] height: u32,
+    #[string] encoding: String,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createTexture called before setup");
+
+    let (rgba, width, height) = match encoding.as_str() {
+        "raw" => (data.to_vec(), width, height),
+        "png" | "jpeg" => {
+            let decoded = image::load_from_memory(data)
+                .expect("Failed to decode image")
+                .to_rgba8();
+            let (decoded_width, decoded_height) = decoded.dimensions();
+            (decoded.into_raw(), decoded_width, decoded_height)
+        }
+        other => panic!("Unknown texture encoding: {other}"),
+    };
+
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+
+    let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("User Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    gpu.queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &rgba,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let id = gpu.textures.len() as u32;
+    gpu.textures.push(texture);
+    gpu.texture_views.push(view);
+    id
+}
+
+/// Upload raw RGBA bytes into an already-created texture via
+/// `queue.write_texture`, replacing its full contents at the given size.
+#[op2(fast)]
+pub fn op_gpu_write_texture(
+    state: &mut OpState,
+    #[smi] texture_id: u32,
+    #[buffer] data: &[u8],
+    #[smi] width: u32,
+    #[smi] height: u32,
+) {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - writeTexture called before setup");
+
+    let texture = &gpu.textures[texture_id as usize];
+    gpu.queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+/// Create a sampler with the given filter modes (`"linear"` / `"nearest"`)
+/// and address mode (`"clamp"` / `"repeat"` / `"mirror"`, applied to all
+/// three axes). Returns a handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_sampler(
+    state: &mut OpState,
+    #[string] mag_filter: String,
+    #[string] min_filter: String,
+    #[string] address_mode: String,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createSampler called before setup");
+
+    let filter_mode = |mode: &str| match mode {
+        "linear" => wgpu::FilterMode::Linear,
+        "nearest" => wgpu::FilterMode::Nearest,
+        other => panic!("Unknown filter mode: {other}"),
+    };
+    let address = match address_mode.as_str() {
+        "clamp" => wgpu::AddressMode::ClampToEdge,
+        "repeat" => wgpu::AddressMode::Repeat,
+        "mirror" => wgpu::AddressMode::MirrorRepeat,
+        other => panic!("Unknown address mode: {other}"),
+    };
+
+    let sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("User Sampler"),
+        address_mode_u: address,
+        address_mode_v: address,
+        address_mode_w: address,
+        mag_filter: filter_mode(&mag_filter),
+        min_filter: filter_mode(&min_filter),
+        ..Default::default()
+    });
+
+    let id = gpu.samplers.len() as u32;
+    gpu.samplers.push(sampler);
+    id
+}
+
+/// Create a compute pipeline from a shader module handle, an entry point,
+/// and the bind group layouts its storage buffers are bound through. Returns
+/// a handle ID.
+#[op2(fast)]
+#[smi]
+pub fn op_gpu_create_compute_pipeline(
+    state: &mut OpState,
+    #[smi] shader_module_id: u32,
+    #[string] entry_point: String,
+    #[serde] bind_group_layout_ids: Vec<u32>,
+) -> u32 {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - createComputePipeline called before setup");
+
+    let shader = &gpu.shader_modules[shader_module_id as usize];
+    let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = bind_group_layout_ids
+        .iter()
+        .map(|id| &gpu.bind_group_layouts[*id as usize])
+        .collect();
+
+    let pipeline_layout = gpu
+        .device
+        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Compute Pipeline Layout"),
+            bind_group_layouts: &bind_group_layouts,
+            ..Default::default()
+        });
+
+    let pipeline = gpu
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Compute Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: shader,
+            entry_point: Some(&entry_point),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+    let id = gpu.compute_pipelines.len() as u32;
+    gpu.compute_pipelines.push(pipeline);
+    id
+}
+
+/// Encode and submit a compute pass: bind the pipeline and bind groups, then
+/// `dispatch_workgroups(x, y, z)`.
+#[op2(fast)]
+pub fn op_gpu_dispatch(
+    state: &mut OpState,
+    #[smi] pipeline_id: u32,
+    #[serde] bind_group_ids: Vec<u32>,
+    #[smi] x: u32,
+    #[smi] y: u32,
+    #[smi] z: u32,
+) {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - dispatch called before setup");
+
+    let mut encoder = gpu
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Compute Encoder"),
+        });
+
+    {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Compute Pass"),
+            timestamp_writes: None,
+        });
+
+        let pipeline = &gpu.compute_pipelines[pipeline_id as usize];
+        compute_pass.set_pipeline(pipeline);
+        for (index, bind_group_id) in bind_group_ids.iter().enumerate() {
+            compute_pass.set_bind_group(
+                index as u32,
+                &gpu.bind_groups[*bind_group_id as usize],
+                &[],
+            );
+        }
+        compute_pass.dispatch_workgroups(x, y, z);
+    }
+
+    gpu.queue.submit(std::iter::once(encoder.finish()));
+}
+
+/// Copy a storage buffer into a `MAP_READ` staging buffer and read its bytes
+/// back to JavaScript. Blocks on `device.poll(Maintain::Wait)` until the
+/// `map_async` callback fires, so results are available synchronously.
+#[op2]
+#[buffer]
+pub fn op_gpu_read_buffer(
+    state: &mut OpState,
+    #[smi] buffer_id: u32,
+    #[number] size: u64,
+) -> Vec<u8> {
+    let gpu_state_rc = state.borrow::<SharedGpuState>().clone();
+    let mut gpu_state_opt = gpu_state_rc.borrow_mut();
+    let gpu = gpu_state_opt
+        .as_mut()
+        .expect("GPU not initialized - readBuffer called before setup");
+
+    let staging_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Staging Buffer"),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = gpu
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Readback Encoder"),
+        });
+    encoder.copy_buffer_to_buffer(&gpu.buffers[buffer_id as usize], 0, &staging_buffer, 0, size);
+    gpu.queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = staging_buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    gpu.device.poll(wgpu::Maintain::Wait);
+    rx.recv()
+        .expect("map_async callback never fired")
+        .expect("Failed to map staging buffer for reading");
+
+    let data = slice.get_mapped_range().to_vec();
+    staging_buffer.unmap();
+    data
+}
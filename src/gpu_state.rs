@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Configuration for the window, set from JS before the event loop starts.
@@ -18,6 +19,31 @@ impl Default for WindowConfig {
     }
 }
 
+/// A single widget recorded by the `op_gui_*` ops during a JS `gui()`
+/// callback. Replayed into an `egui::Window` once the callback returns.
+#[derive(Clone)]
+pub enum GuiWidgetDesc {
+    Label(String),
+    Slider {
+        label: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    Button {
+        label: String,
+    },
+}
+
+/// The result of a widget's interaction during the last egui render, keyed
+/// by its label. `op_gui_slider`/`op_gui_button` read this back so widget
+/// state round-trips from one frame to the next.
+#[derive(Clone, Copy)]
+pub enum GuiInteraction {
+    Slider(f64),
+    Button(bool),
+}
+
 /// Holds all wgpu resources. Created during `resumed()` and shared
 /// with deno_core ops via `Rc<RefCell<Option<GpuState>>>`.
 pub struct GpuState {
@@ -28,6 +54,25 @@ pub struct GpuState {
     pub config: wgpu::SurfaceConfiguration,
     pub shader_modules: Vec<wgpu::ShaderModule>,
     pub render_pipelines: Vec<wgpu::RenderPipeline>,
+    /// Whether `render_pipelines[i]` was created with `depth_stencil` set,
+    /// kept in lockstep with `render_pipelines` by index. `drawFrame`/
+    /// `drawIndexed` consult this so a depth-less pipeline never gets a
+    /// depth-stencil attachment just because some *other* pipeline uses one.
+    pub render_pipeline_has_depth: Vec<bool>,
+    pub buffers: Vec<wgpu::Buffer>,
+    pub bind_group_layouts: Vec<wgpu::BindGroupLayout>,
+    pub bind_groups: Vec<wgpu::BindGroup>,
+    pub textures: Vec<wgpu::Texture>,
+    pub texture_views: Vec<wgpu::TextureView>,
+    pub samplers: Vec<wgpu::Sampler>,
+    pub compute_pipelines: Vec<wgpu::ComputePipeline>,
+    pub depth_format: Option<wgpu::TextureFormat>,
+    pub depth_texture: Option<wgpu::Texture>,
+    pub depth_view: Option<wgpu::TextureView>,
+    pub egui_renderer: egui_wgpu::Renderer,
+    pub egui_widgets: Vec<GuiWidgetDesc>,
+    pub egui_interactions: HashMap<String, GuiInteraction>,
+    pub pending_egui_output: Option<(Vec<egui::ClippedPrimitive>, egui::TexturesDelta, f32)>,
 }
 
 impl GpuState {
@@ -57,6 +102,8 @@ impl GpuState {
         config.present_mode = wgpu::PresentMode::AutoVsync;
         surface.configure(&device, &config);
 
+        let egui_renderer = egui_wgpu::Renderer::new(&device, config.format, None, 1, true);
+
         Self {
             window,
             surface,
@@ -65,6 +112,21 @@ impl GpuState {
             config,
             shader_modules: Vec::new(),
             render_pipelines: Vec::new(),
+            render_pipeline_has_depth: Vec::new(),
+            buffers: Vec::new(),
+            bind_group_layouts: Vec::new(),
+            bind_groups: Vec::new(),
+            textures: Vec::new(),
+            texture_views: Vec::new(),
+            samplers: Vec::new(),
+            compute_pipelines: Vec::new(),
+            depth_format: None,
+            depth_texture: None,
+            depth_view: None,
+            egui_renderer,
+            egui_widgets: Vec::new(),
+            egui_interactions: HashMap::new(),
+            pending_egui_output: None,
         }
     }
 
@@ -74,6 +136,42 @@ impl GpuState {
             self.config.width = width;
             self.config.height = height;
             self.surface.configure(&self.device, &self.config);
+            self.recreate_depth_texture();
         }
     }
+
+    /// Remember `format` as the depth format to use and (re)create the depth
+    /// texture at the current surface size. Called each time a pipeline is
+    /// created with depth testing enabled; the most recent format wins.
+    pub fn ensure_depth_texture(&mut self, format: wgpu::TextureFormat) {
+        self.depth_format = Some(format);
+        self.recreate_depth_texture();
+    }
+
+    /// Recreate the depth texture (and its view) at the current surface
+    /// size, using the remembered `depth_format`. No-op if depth testing
+    /// hasn't been enabled yet.
+    fn recreate_depth_texture(&mut self) {
+        let Some(format) = self.depth_format else {
+            return;
+        };
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Depth Texture"),
+            size: wgpu::Extent3d {
+                width: self.config.width.max(1),
+                height: self.config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        self.depth_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        self.depth_texture = Some(texture);
+    }
 }
@@ -17,7 +17,7 @@ use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, EventLoop};
 use winit::window::{WindowAttributes, WindowId};
 
-use crate::gpu_state::{GpuState, WindowConfig};
+use crate::gpu_state::{GpuState, GuiInteraction, GuiWidgetDesc, WindowConfig};
 
 // ---------- TypeScript module loader (from blog post pt.2) ----------
 
@@ -115,6 +115,20 @@ extension!(
         gpu_ops::op_gpu_create_shader_module,
         gpu_ops::op_gpu_create_render_pipeline,
         gpu_ops::op_gpu_draw_frame,
+        gpu_ops::op_gpu_create_buffer,
+        gpu_ops::op_gpu_draw_indexed,
+        gpu_ops::op_gpu_create_bind_group_layout,
+        gpu_ops::op_gpu_create_bind_group,
+        gpu_ops::op_gpu_write_buffer,
+        gpu_ops::op_gpu_create_texture,
+        gpu_ops::op_gpu_write_texture,
+        gpu_ops::op_gpu_create_sampler,
+        gpu_ops::op_gpu_create_compute_pipeline,
+        gpu_ops::op_gpu_dispatch,
+        gpu_ops::op_gpu_read_buffer,
+        gpu_ops::op_gui_label,
+        gpu_ops::op_gui_slider,
+        gpu_ops::op_gui_button,
     ],
     esm_entry_point = "ext:gpu_runtime/runtime.js",
     esm = [dir "src", "runtime.js"],
@@ -127,6 +141,8 @@ struct App {
     gpu_state: Rc<RefCell<Option<GpuState>>>,
     window_config: WindowConfig,
     setup_done: bool,
+    egui_ctx: egui::Context,
+    egui_state: Option<egui_winit::State>,
 }
 
 impl ApplicationHandler for App {
@@ -155,6 +171,16 @@ impl ApplicationHandler for App {
         // Store GPU state (shared with ops)
         *self.gpu_state.borrow_mut() = Some(gpu);
 
+        // Initialize egui's winit integration now that the window exists
+        self.egui_state = Some(egui_winit::State::new(
+            self.egui_ctx.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            Some(window.scale_factor() as f32),
+            window.theme(),
+            None,
+        ));
+
         // Call JS setup callback
         let result = self
             .js_runtime
@@ -175,6 +201,16 @@ impl ApplicationHandler for App {
         _window_id: WindowId,
         event: WindowEvent,
     ) {
+        // Feed the event to egui first so it can consume input meant for
+        // the debug overlay before the gameplay/demo handlers below see it.
+        let mut egui_consumed = false;
+        if let Some(egui_state) = self.egui_state.as_mut() {
+            let gpu_opt = self.gpu_state.borrow();
+            if let Some(gpu) = gpu_opt.as_ref() {
+                egui_consumed = egui_state.on_window_event(&gpu.window, &event).consumed;
+            }
+        }
+
         match event {
             WindowEvent::CloseRequested => {
                 event_loop.exit();
@@ -196,7 +232,74 @@ impl ApplicationHandler for App {
                 let _ = self.js_runtime.execute_script("<resize>", script);
             }
 
+            WindowEvent::KeyboardInput { event, .. } => {
+                let code = match event.physical_key {
+                    winit::keyboard::PhysicalKey::Code(key_code) => format!("{:?}", key_code),
+                    winit::keyboard::PhysicalKey::Unidentified(_) => "Unidentified".to_string(),
+                };
+                let pressed = event.state == winit::event::ElementState::Pressed;
+
+                // Call JS key callback, unless egui consumed this event (e.g.
+                // the debug overlay has keyboard focus on a text field).
+                if !egui_consumed {
+                    let script = format!(
+                        "globalThis.__gpuCallbacks.onKey?.({:?}, {})",
+                        code, pressed
+                    );
+                    let _ = self.js_runtime.execute_script("<key>", script);
+                }
+            }
+
+            WindowEvent::CursorMoved { position, .. } => {
+                // Call JS mouse-move callback, unless egui consumed this event.
+                if !egui_consumed {
+                    let script = format!(
+                        "globalThis.__gpuCallbacks.onMouseMove?.({}, {})",
+                        position.x, position.y
+                    );
+                    let _ = self.js_runtime.execute_script("<mousemove>", script);
+                }
+            }
+
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button_code = match button {
+                    winit::event::MouseButton::Left => 0,
+                    winit::event::MouseButton::Right => 1,
+                    winit::event::MouseButton::Middle => 2,
+                    winit::event::MouseButton::Other(code) => code as i64,
+                    _ => -1,
+                };
+                let pressed = state == winit::event::ElementState::Pressed;
+
+                // Call JS mouse-button callback, unless egui consumed this
+                // event (e.g. the user clicked a debug widget).
+                if !egui_consumed {
+                    let script = format!(
+                        "globalThis.__gpuCallbacks.onMouseButton?.({}, {})",
+                        button_code, pressed
+                    );
+                    let _ = self.js_runtime.execute_script("<mousebutton>", script);
+                }
+            }
+
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (dx, dy) = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => (x as f64, y as f64),
+                    winit::event::MouseScrollDelta::PixelDelta(pos) => (pos.x, pos.y),
+                };
+
+                // Call JS scroll callback, unless egui consumed this event
+                // (e.g. the user scrolled a debug slider into focus).
+                if !egui_consumed {
+                    let script =
+                        format!("globalThis.__gpuCallbacks.onScroll?.({}, {})", dx, dy);
+                    let _ = self.js_runtime.execute_script("<scroll>", script);
+                }
+            }
+
             WindowEvent::RedrawRequested => {
+                self.run_egui_frame();
+
                 // Call JS draw callback
                 let result = self
                     .js_runtime
@@ -219,6 +322,87 @@ impl ApplicationHandler for App {
     }
 }
 
+impl App {
+    /// Run one egui frame: call the JS `gui()` callback to collect widget
+    /// descriptors (and resolve last frame's interactions), replay them into
+    /// a debug window, then stash the tessellated output on `GpuState` for
+    /// `op_gpu_draw_frame`/`op_gpu_draw_indexed` to render.
+    fn run_egui_frame(&mut self) {
+        let Some(egui_state) = self.egui_state.as_mut() else {
+            return;
+        };
+        let window = match self.gpu_state.borrow().as_ref() {
+            Some(gpu) => gpu.window.clone(),
+            None => return,
+        };
+
+        if let Some(gpu) = self.gpu_state.borrow_mut().as_mut() {
+            gpu.egui_widgets.clear();
+        }
+
+        // Call the JS gui callback; op_gui_label/slider/button push widget
+        // descriptors into gpu.egui_widgets as a side effect.
+        let _ = self
+            .js_runtime
+            .execute_script("<gui>", "globalThis.__gpuCallbacks.gui?.()");
+
+        let widgets = match self.gpu_state.borrow().as_ref() {
+            Some(gpu) => gpu.egui_widgets.clone(),
+            None => return,
+        };
+
+        // No script registered a `gpu.onGui(...)` callback (or it registered
+        // no widgets) — skip showing the Debug window so demos that don't
+        // use the GUI feature don't get an empty overlay drawn every frame.
+        if widgets.is_empty() {
+            if let Some(gpu) = self.gpu_state.borrow_mut().as_mut() {
+                gpu.pending_egui_output = None;
+            }
+            return;
+        }
+
+        let raw_input = egui_state.take_egui_input(&window);
+        let mut interactions = std::collections::HashMap::new();
+        let full_output = self.egui_ctx.run(raw_input, |ctx| {
+            egui::Window::new("Debug").show(ctx, |ui| {
+                for widget in &widgets {
+                    match widget {
+                        GuiWidgetDesc::Label(text) => {
+                            ui.label(text);
+                        }
+                        GuiWidgetDesc::Slider {
+                            label,
+                            value,
+                            min,
+                            max,
+                        } => {
+                            let mut v = *value;
+                            ui.add(egui::Slider::new(&mut v, *min..=*max).text(label));
+                            interactions.insert(label.clone(), GuiInteraction::Slider(v));
+                        }
+                        GuiWidgetDesc::Button { label } => {
+                            let clicked = ui.button(label).clicked();
+                            interactions.insert(label.clone(), GuiInteraction::Button(clicked));
+                        }
+                    }
+                }
+            });
+        });
+
+        egui_state.handle_platform_output(&window, full_output.platform_output);
+
+        let paint_jobs = self
+            .egui_ctx
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+
+        if let Some(gpu) = self.gpu_state.borrow_mut().as_mut() {
+            gpu.egui_interactions = interactions;
+            gpu.pending_egui_output =
+                Some((paint_jobs, full_output.textures_delta, full_output.pixels_per_point));
+        }
+    }
+}
+
 // ---------- Main ----------
 
 fn main() {
@@ -290,6 +474,8 @@ fn main() {
         gpu_state,
         window_config,
         setup_done: false,
+        egui_ctx: egui::Context::default(),
+        egui_state: None,
     };
 
     event_loop.run_app(&mut app).expect("Event loop failed");